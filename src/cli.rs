@@ -0,0 +1,182 @@
+use std::io::BufRead;
+use std::path::Path;
+
+use clap::Parser;
+
+use crate::{ModuleFile, Project};
+
+/// Analyze the import graph of a JS/TS project.
+#[derive(Parser, Debug)]
+#[command(name = "jsa", about = "Analyze JS/TS module import graphs")]
+pub struct Cli {
+    /// Entry files or glob patterns to analyze (e.g. `src/**/*.ts`). Reads
+    /// newline-separated paths from stdin if none are given.
+    pub entries: Vec<String>,
+
+    /// Path to a tsconfig.json (`compilerOptions.paths`) for alias resolution.
+    #[arg(long, conflicts_with = "import_map")]
+    pub tsconfig: Option<String>,
+
+    /// Path to an import-map JSON file for alias resolution.
+    #[arg(long)]
+    pub import_map: Option<String>,
+
+    /// Write the combined JSON graph here instead of stdout.
+    #[arg(long)]
+    pub output_file: Option<String>,
+
+    /// Write one analysis file per input alongside the combined graph, like
+    /// lightningcss's batch `-d`/`--output-dir` mode.
+    #[arg(long)]
+    pub output_dir: Option<String>,
+}
+
+impl Cli {
+    pub fn import_map_path(&self) -> Option<&str> {
+        self.tsconfig.as_deref().or(self.import_map.as_deref())
+    }
+}
+
+/// Expand glob patterns among `cli.entries` into concrete paths, falling
+/// back to newline-separated paths on stdin when no entries were given.
+pub fn resolve_entries(cli: &Cli) -> Vec<String> {
+    if cli.entries.is_empty() {
+        return read_stdin_entries();
+    }
+    let mut entries = Vec::new();
+    for entry in &cli.entries {
+        if is_glob_pattern(entry) {
+            if let Ok(paths) = glob::glob(entry) {
+                entries.extend(
+                    paths
+                        .filter_map(Result::ok)
+                        .map(|p| p.to_string_lossy().into_owned()),
+                );
+            }
+        } else {
+            entries.push(entry.clone());
+        }
+    }
+    entries
+}
+
+fn is_glob_pattern(entry: &str) -> bool {
+    entry.contains(['*', '?', '['])
+}
+
+fn read_stdin_entries() -> Vec<String> {
+    std::io::stdin()
+        .lock()
+        .lines()
+        .map_while(Result::ok)
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Write the combined graph to `--output-file` or stdout, and, if
+/// `--output-dir` was given, additionally write one analysis file per input
+/// plus the combined graph into that directory.
+pub fn write_output(cli: &Cli, project: &Project) -> std::io::Result<()> {
+    let json = serde_json::to_string_pretty(project)?;
+    match &cli.output_file {
+        Some(path) => std::fs::write(path, &json)?,
+        None => println!("{json}"),
+    }
+
+    if let Some(dir) = &cli.output_dir {
+        std::fs::create_dir_all(dir)?;
+        for file in &project.files {
+            write_module_file(dir, file)?;
+        }
+        std::fs::write(Path::new(dir).join("jsa.json"), &json)?;
+    }
+
+    Ok(())
+}
+
+fn write_module_file(dir: &str, file: &ModuleFile) -> std::io::Result<()> {
+    let name = sanitize_file_name(&file.path);
+    let path = Path::new(dir).join(format!("{name}.json"));
+    std::fs::write(path, serde_json::to_string_pretty(file)?)
+}
+
+/// Turn a path into a safe, flat file name by replacing separators.
+fn sanitize_file_name(path: &str) -> String {
+    path.chars()
+        .map(|c| {
+            if c == '/' || c == '\\' || c == ':' {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// A scratch directory under the OS temp dir, unique per test and wiped
+    /// before use so re-runs don't see stale files from a previous run.
+    fn temp_project(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("jsa-cli-test-{name}"));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn bare_cli(entries: Vec<String>) -> Cli {
+        Cli {
+            entries,
+            tsconfig: None,
+            import_map: None,
+            output_file: None,
+            output_dir: None,
+        }
+    }
+
+    #[test]
+    fn resolve_entries_expands_globs_and_passes_through_plain_paths() {
+        let dir = temp_project("glob-entries");
+        fs::write(dir.join("a.ts"), "export const a = 1;").unwrap();
+        fs::write(dir.join("b.ts"), "export const b = 1;").unwrap();
+        let cli = bare_cli(vec![
+            dir.join("*.ts").to_string_lossy().into_owned(),
+            "explicit.ts".to_string(),
+        ]);
+
+        let entries: std::collections::BTreeSet<String> =
+            resolve_entries(&cli).into_iter().collect();
+
+        let expected: std::collections::BTreeSet<String> = vec![
+            dir.join("a.ts").to_string_lossy().into_owned(),
+            dir.join("b.ts").to_string_lossy().into_owned(),
+            "explicit.ts".to_string(),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(entries, expected);
+    }
+
+    #[test]
+    fn write_output_writes_per_file_analysis_plus_combined_graph() {
+        let dir = temp_project("output-dir");
+        let mut cli = bare_cli(vec![]);
+        cli.output_dir = Some(dir.to_string_lossy().into_owned());
+        let project = Project {
+            files: vec![ModuleFile {
+                path: "src/a.ts".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        write_output(&cli, &project).unwrap();
+
+        assert!(dir.join("src_a.ts.json").is_file());
+        assert!(dir.join("jsa.json").is_file());
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+use crate::ModuleFile;
+
+/// A cache of previously-analyzed files, keyed by resolved path, each
+/// guarded by a content hash of its source text — modeled on Deno's
+/// lockfile. Lets `Project::traverse` skip reparsing and re-visiting files
+/// that haven't changed since the last run.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct Lockfile {
+    entries: HashMap<String, LockEntry>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct LockEntry {
+    /// `sha256-<hex>` integrity hash of the file's source text together with
+    /// the resolver's config fingerprint.
+    integrity: String,
+    module: ModuleFile,
+}
+
+impl Lockfile {
+    /// Load a lockfile from disk, or an empty one if it doesn't exist yet or
+    /// fails to parse.
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) {
+        if let Ok(json) = serde_json::to_string_pretty(self) {
+            let _ = std::fs::write(path, json);
+        }
+    }
+
+    /// Return the cached analysis for `resolved_path` if `source_text` and
+    /// `config_fingerprint` (the resolver's alias/base-dir config — see
+    /// [`crate::resolver::Resolver::config_fingerprint`]) still hash to the
+    /// same integrity value recorded last time. Keying on the config too
+    /// means a changed `--tsconfig`/`--import-map` invalidates cached
+    /// `resolved_path`s even though the source text itself is unchanged.
+    pub fn lookup(
+        &self,
+        resolved_path: &str,
+        source_text: &str,
+        config_fingerprint: &str,
+    ) -> Option<ModuleFile> {
+        let entry = self.entries.get(resolved_path)?;
+        if entry.integrity == integrity_hash(source_text, config_fingerprint) {
+            Some(entry.module.clone())
+        } else {
+            None
+        }
+    }
+
+    pub fn insert(
+        &mut self,
+        resolved_path: String,
+        source_text: &str,
+        config_fingerprint: &str,
+        module: ModuleFile,
+    ) {
+        self.entries.insert(
+            resolved_path,
+            LockEntry {
+                integrity: integrity_hash(source_text, config_fingerprint),
+                module,
+            },
+        );
+    }
+}
+
+fn integrity_hash(source_text: &str, config_fingerprint: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(source_text.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(config_fingerprint.as_bytes());
+    let digest = hasher.finalize();
+    let mut hex = String::with_capacity(digest.len() * 2);
+    for byte in digest {
+        hex.push_str(&format!("{byte:02x}"));
+    }
+    format!("sha256-{hex}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hits_cache_when_source_and_config_are_unchanged() {
+        let mut lockfile = Lockfile::default();
+        lockfile.insert(
+            "a.ts".to_string(),
+            "export const x = 1;",
+            "fingerprint-a",
+            ModuleFile::default(),
+        );
+
+        assert!(lockfile
+            .lookup("a.ts", "export const x = 1;", "fingerprint-a")
+            .is_some());
+    }
+
+    #[test]
+    fn misses_cache_when_source_text_changes() {
+        let mut lockfile = Lockfile::default();
+        lockfile.insert(
+            "a.ts".to_string(),
+            "export const x = 1;",
+            "fingerprint-a",
+            ModuleFile::default(),
+        );
+
+        assert!(lockfile
+            .lookup("a.ts", "export const x = 2;", "fingerprint-a")
+            .is_none());
+    }
+
+    #[test]
+    fn misses_cache_when_config_fingerprint_changes() {
+        let mut lockfile = Lockfile::default();
+        lockfile.insert(
+            "a.ts".to_string(),
+            "export const x = 1;",
+            "fingerprint-a",
+            ModuleFile::default(),
+        );
+
+        assert!(lockfile
+            .lookup("a.ts", "export const x = 1;", "fingerprint-b")
+            .is_none());
+    }
+
+    #[test]
+    fn misses_cache_for_a_resolved_path_never_inserted() {
+        let lockfile = Lockfile::default();
+
+        assert!(lockfile
+            .lookup("missing.ts", "export const x = 1;", "fingerprint-a")
+            .is_none());
+    }
+}
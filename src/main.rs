@@ -1,28 +1,69 @@
-use std::path::Path;
+mod cli;
+mod lockfile;
+mod resolver;
 
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use clap::Parser as _;
 use oxc::allocator::Allocator;
-use oxc::ast::{AstKind, Visit};
+use oxc::ast::ast::{BindingPatternKind, Declaration, ExportDefaultDeclarationKind};
+use oxc::ast::AstKind;
+use oxc::ast_visit::Visit;
 use oxc::parser::Parser;
 use oxc::span::SourceType;
 
-#[derive(Debug, Default, serde::Serialize)]
+use cli::Cli;
+use lockfile::Lockfile;
+use resolver::Resolver;
+
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
 struct ImportSpecifier {
     source_name: String,
     local_name: String,
 }
 
-#[derive(Debug, Default, serde::Serialize)]
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
 struct ImportItem {
+    /// The specifier exactly as written in the source, e.g. `"./foo"`.
     source: String,
+    /// The specifier resolved to an absolute file path, if resolution
+    /// succeeded.
+    resolved_path: Option<String>,
     specifiers: Vec<ImportSpecifier>,
 }
 
-#[derive(Debug, Default, serde::Serialize)]
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+struct ExportItem {
+    /// The exported name, or `"*"` for a blanket `export * from "./m"`.
+    name: String,
+    /// The module this export is re-exported from, if it is a re-export
+    /// rather than a local declaration.
+    re_exported_from: Option<String>,
+    /// `re_exported_from` resolved to an absolute file path, if resolution
+    /// succeeded. Lets a re-export edge be traversed like a regular import
+    /// and count as usage of the target's export.
+    resolved_path: Option<String>,
+    /// For `export { x as y } from "./m"`, the name (`x`) as it exists in
+    /// `./m` — distinct from `name` (`y`), the name consumers of *this*
+    /// file see. `None` for local declarations and for `export * from`.
+    re_exported_name: Option<String>,
+    /// Whether this entry is `export * from "./m"` or `export * as ns from
+    /// "./m"` — a whole-module re-export that counts every export of the
+    /// target as used, rather than just the one named in `re_exported_name`.
+    is_namespace_reexport: bool,
+}
+
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
 struct ModuleFile {
     path: String,
     imports: Vec<ImportItem>,
-    exports: Vec<String>,
+    exports: Vec<ExportItem>,
     default_export: Option<String>,
+    /// Export names (and `"default"` for the default export) that no other
+    /// file in the graph imports. Populated after traversal; always empty
+    /// for entry files, since those are roots whose exports are the point.
+    unused_exports: Vec<String>,
 }
 
 impl<'a> Visit<'a> for ModuleFile {
@@ -32,6 +73,7 @@ impl<'a> Visit<'a> for ModuleFile {
                 let from = &import.source.value;
                 self.imports.push(ImportItem {
                     source: from.to_string(),
+                    resolved_path: None,
                     specifiers: Vec::new(),
                 });
             }
@@ -60,61 +102,487 @@ impl<'a> Visit<'a> for ModuleFile {
                     local_name: local.to_string(),
                 });
             }
+            AstKind::ExportNamedDeclaration(export) => {
+                let from = export.source.as_ref().map(|s| s.value.to_string());
+                for specifier in &export.specifiers {
+                    self.exports.push(ExportItem {
+                        name: specifier.exported.name().to_string(),
+                        re_exported_from: from.clone(),
+                        resolved_path: None,
+                        re_exported_name: from.as_ref().map(|_| specifier.local.name().to_string()),
+                        is_namespace_reexport: false,
+                    });
+                }
+                if let Some(declaration) = &export.declaration {
+                    self.exports
+                        .extend(export_names_from_declaration(declaration));
+                }
+            }
+            AstKind::ExportDefaultDeclaration(export) => {
+                self.default_export = Some(default_export_name(&export.declaration));
+            }
+            AstKind::ExportAllDeclaration(export) => {
+                let name = export
+                    .exported
+                    .as_ref()
+                    .map(|e| e.name().to_string())
+                    .unwrap_or_else(|| "*".to_string());
+                self.exports.push(ExportItem {
+                    name,
+                    re_exported_from: Some(export.source.value.to_string()),
+                    resolved_path: None,
+                    re_exported_name: None,
+                    is_namespace_reexport: true,
+                });
+            }
             _ => {}
         }
     }
 }
 
+/// Pull the names a local `export const x`/`function f`/`class C`/etc.
+/// declaration introduces, so they can be recorded as plain (non-re-export)
+/// exports.
+fn export_names_from_declaration(declaration: &Declaration) -> Vec<ExportItem> {
+    let names: Vec<String> = match declaration {
+        Declaration::VariableDeclaration(var_decl) => var_decl
+            .declarations
+            .iter()
+            .filter_map(|d| binding_name(&d.id.kind))
+            .collect(),
+        Declaration::FunctionDeclaration(func) => func
+            .id
+            .as_ref()
+            .map(|id| id.name.to_string())
+            .into_iter()
+            .collect(),
+        Declaration::ClassDeclaration(class) => class
+            .id
+            .as_ref()
+            .map(|id| id.name.to_string())
+            .into_iter()
+            .collect(),
+        Declaration::TSTypeAliasDeclaration(alias) => vec![alias.id.name.to_string()],
+        Declaration::TSInterfaceDeclaration(iface) => vec![iface.id.name.to_string()],
+        Declaration::TSEnumDeclaration(decl) => vec![decl.id.name.to_string()],
+        _ => Vec::new(),
+    };
+    names
+        .into_iter()
+        .map(|name| ExportItem {
+            name,
+            re_exported_from: None,
+            resolved_path: None,
+            re_exported_name: None,
+            is_namespace_reexport: false,
+        })
+        .collect()
+}
+
+fn binding_name(kind: &BindingPatternKind) -> Option<String> {
+    match kind {
+        BindingPatternKind::BindingIdentifier(id) => Some(id.name.to_string()),
+        _ => None,
+    }
+}
+
+/// `export default ...` carries a name only when the declaration is a named
+/// function/class; anything else (an expression, an anonymous function) is
+/// recorded under the synthetic `"default"` marker.
+fn default_export_name(kind: &ExportDefaultDeclarationKind) -> String {
+    match kind {
+        ExportDefaultDeclarationKind::FunctionDeclaration(func) => func
+            .id
+            .as_ref()
+            .map(|id| id.name.to_string())
+            .unwrap_or_else(|| "default".to_string()),
+        ExportDefaultDeclarationKind::ClassDeclaration(class) => class
+            .id
+            .as_ref()
+            .map(|id| id.name.to_string())
+            .unwrap_or_else(|| "default".to_string()),
+        _ => "default".to_string(),
+    }
+}
+
+/// A dependency cycle discovered while traversing the import graph, e.g.
+/// `a.ts` importing `b.ts` which imports back into `a.ts`.
+#[derive(Debug, serde::Serialize)]
+struct CircularImport {
+    from: String,
+    to: String,
+    /// The full cycle, starting at `to` and ending back at `to`.
+    chain: Vec<String>,
+}
+
+/// A file in the process of being parsed: its partially-filled `ModuleFile`
+/// plus the resolved imports it still needs to visit, kept on the explicit
+/// work-stack so cycles can be detected by path membership on that stack.
+struct Frame {
+    ast: ModuleFile,
+    pending: Vec<String>,
+}
+
 #[derive(Debug, Default, serde::Serialize)]
 struct Project {
     files: Vec<ModuleFile>,
+    circular_imports: Vec<CircularImport>,
+    /// Paths of files that have at least one export nothing in the graph
+    /// imports; see [`ModuleFile::unused_exports`] for the per-file detail.
+    unused_exports_summary: Vec<String>,
 }
 
 impl Project {
-    fn traverse(&mut self, files: Vec<String>) {
-        for file in files {
-            if self.files.iter().any(|f| f.path == file) {
-                continue;
+    fn traverse(
+        &mut self,
+        files: Vec<String>,
+        lockfile: &mut Lockfile,
+        import_map: HashMap<String, String>,
+        import_map_base: PathBuf,
+    ) {
+        let resolver = Resolver::with_import_map(import_map, import_map_base);
+        // Canonicalize seed entries so an entry that's also reachable via
+        // import/re-export (stored at its canonical resolved path) dedupes
+        // onto the same graph node instead of being analyzed twice.
+        let files: Vec<String> = files
+            .into_iter()
+            .map(|file| resolver::normalize(Path::new(&file)).to_string_lossy().into_owned())
+            .collect();
+        let roots: HashSet<String> = files.iter().cloned().collect();
+        let mut stack: Vec<Frame> = Vec::new();
+        let mut in_progress: HashSet<String> = HashSet::new();
+        let mut finished: HashSet<String> = HashSet::new();
+        let mut queue: VecDeque<String> = files.into();
+
+        loop {
+            if let Some(top) = stack.last_mut() {
+                match top.pending.pop() {
+                    Some(next) => {
+                        if finished.contains(&next) {
+                            continue;
+                        }
+                        if in_progress.contains(&next) {
+                            let idx = stack.iter().position(|f| f.ast.path == next).unwrap();
+                            let mut chain: Vec<String> =
+                                stack[idx..].iter().map(|f| f.ast.path.clone()).collect();
+                            chain.push(next.clone());
+                            self.circular_imports.push(CircularImport {
+                                from: stack.last().unwrap().ast.path.clone(),
+                                to: next,
+                                chain,
+                            });
+                            continue;
+                        }
+                        if let Some(frame) = Self::parse_file(&resolver, lockfile, &next) {
+                            in_progress.insert(next);
+                            stack.push(frame);
+                        }
+                    }
+                    None => {
+                        let frame = stack.pop().unwrap();
+                        in_progress.remove(&frame.ast.path);
+                        finished.insert(frame.ast.path.clone());
+                        self.files.push(frame.ast);
+                    }
+                }
+            } else if let Some(file) = queue.pop_front() {
+                if finished.contains(&file) {
+                    continue;
+                }
+                if let Some(frame) = Self::parse_file(&resolver, lockfile, &file) {
+                    in_progress.insert(file);
+                    stack.push(frame);
+                }
+            } else {
+                break;
+            }
+        }
+
+        self.mark_unused_exports(&roots);
+    }
+
+    /// Cross-reference every import's `source_name`, and every re-export's
+    /// source name, against the exports of its resolved target, and flag
+    /// exports nothing in the graph imports or re-exports. A namespace
+    /// import (`import * as ns`) or a blanket `export * from` can't be
+    /// tracked member-by-member, so either marks the whole target as used;
+    /// entry files in `roots` are never flagged since their exports are the
+    /// reason they're roots.
+    fn mark_unused_exports(&mut self, roots: &HashSet<String>) {
+        let mut used_names: HashMap<String, HashSet<String>> = HashMap::new();
+        let mut namespace_used: HashSet<String> = HashSet::new();
+
+        for file in &self.files {
+            for import in &file.imports {
+                let Some(target) = &import.resolved_path else {
+                    continue;
+                };
+                for specifier in &import.specifiers {
+                    if specifier.source_name == "*" {
+                        namespace_used.insert(target.clone());
+                    } else {
+                        used_names
+                            .entry(target.clone())
+                            .or_default()
+                            .insert(specifier.source_name.clone());
+                    }
+                }
             }
-            let path = Path::new(&file);
-            // if file is not found, skip it
-            if !path.exists() {
-                println!("file not found: {}", file);
+            for export in &file.exports {
+                let Some(target) = &export.resolved_path else {
+                    continue;
+                };
+                if export.is_namespace_reexport {
+                    namespace_used.insert(target.clone());
+                } else if let Some(source_name) = &export.re_exported_name {
+                    used_names
+                        .entry(target.clone())
+                        .or_default()
+                        .insert(source_name.clone());
+                }
+            }
+        }
+
+        for file in &mut self.files {
+            if roots.contains(&file.path) || namespace_used.contains(&file.path) {
                 continue;
             }
-            let source_text = std::fs::read_to_string(path).unwrap();
-            let allocator = Allocator::default();
-            let source_type = SourceType::from_path(path).unwrap();
-            let ret = Parser::new(&allocator, &source_text, source_type).parse();
-
-            for error in ret.errors {
-                let error = error.with_source_code(source_text.clone());
-                println!("{error:?}");
+            let names = used_names.get(&file.path);
+            for export in &file.exports {
+                // A blanket `export *` isn't itself a concrete exported
+                // name a consumer can import, so it can't be "unused".
+                if export.name == "*" {
+                    continue;
+                }
+                if !names.is_some_and(|names| names.contains(&export.name)) {
+                    file.unused_exports.push(export.name.clone());
+                }
             }
+            if file.default_export.is_some()
+                && !names.is_some_and(|names| names.contains("default"))
+            {
+                file.unused_exports.push("default".to_string());
+            }
+        }
 
-            let program = ret.program;
+        self.unused_exports_summary = self
+            .files
+            .iter()
+            .filter(|f| !f.unused_exports.is_empty())
+            .map(|f| f.path.clone())
+            .collect();
+    }
+
+    /// Parse `file`, resolve its imports, and build the work-stack frame for
+    /// it, reusing the cached analysis from `lockfile` when the file's
+    /// content hash hasn't changed since the last run. Returns `None` (and
+    /// prints a diagnostic) if the file is missing.
+    fn parse_file(resolver: &Resolver, lockfile: &mut Lockfile, file: &str) -> Option<Frame> {
+        let path = Path::new(file);
+        // if file is not found, skip it
+        if !path.exists() {
+            println!("file not found: {}", file);
+            return None;
+        }
+        let source_text = std::fs::read_to_string(path).unwrap();
+        let config_fingerprint = resolver.config_fingerprint();
+
+        if let Some(ast_pass) = lockfile.lookup(file, &source_text, &config_fingerprint) {
+            return Some(Self::frame_from(ast_pass));
+        }
 
-            let mut ast_pass = ModuleFile {
-                path: path.to_str().unwrap().to_string(),
-                ..Default::default()
+        let Ok(source_type) = SourceType::from_path(path) else {
+            println!("unsupported file type: {}", file);
+            return None;
+        };
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, &source_text, source_type).parse();
+
+        for error in ret.errors {
+            let error = error.with_source_code(source_text.clone());
+            println!("{error:?}");
+        }
+
+        let program = ret.program;
+
+        let mut ast_pass = ModuleFile {
+            path: path.to_str().unwrap().to_string(),
+            ..Default::default()
+        };
+        ast_pass.visit_program(&program);
+
+        for import in &mut ast_pass.imports {
+            import.resolved_path = resolver
+                .resolve(path, &import.source)
+                .map(|p| p.to_string_lossy().into_owned());
+        }
+        for export in &mut ast_pass.exports {
+            let Some(from) = &export.re_exported_from else {
+                continue;
             };
-            ast_pass.visit_program(&program);
-            let imports = ast_pass
-                .imports
-                .iter()
-                .map(|i| i.source.clone())
-                .collect::<Vec<_>>();
-            self.traverse(imports.clone());
-            self.files.push(ast_pass);
+            export.resolved_path = resolver
+                .resolve(path, from)
+                .map(|p| p.to_string_lossy().into_owned());
         }
+
+        lockfile.insert(
+            file.to_string(),
+            &source_text,
+            &config_fingerprint,
+            ast_pass.clone(),
+        );
+
+        Some(Self::frame_from(ast_pass))
+    }
+
+    /// Reversed so `pending.pop()` visits imports (then re-exports) in
+    /// source order. A re-export's target is traversed exactly like an
+    /// import's, so a module reached only via `export * from`/`export {
+    /// x } from` still gets parsed and checked for unused exports.
+    fn frame_from(ast: ModuleFile) -> Frame {
+        let pending = ast
+            .imports
+            .iter()
+            .filter_map(|i| i.resolved_path.clone())
+            .chain(ast.exports.iter().filter_map(|e| e.resolved_path.clone()))
+            .rev()
+            .collect();
+        Frame { ast, pending }
     }
 }
 
-fn main() {
+fn main() -> std::io::Result<()> {
+    let cli = Cli::parse();
+
+    let lockfile_path = Path::new("jsa.lock.json");
+    let mut lockfile = Lockfile::load(lockfile_path);
+    let (import_map, import_map_base) = cli
+        .import_map_path()
+        .map(|path| resolver::load_import_map(Path::new(path)))
+        .unwrap_or_else(|| (HashMap::new(), PathBuf::from(".")));
+
     let mut project = Project::default();
-    let files = ["test.ts"].iter().map(|s| s.to_string()).collect();
+    let files = cli::resolve_entries(&cli);
+
+    project.traverse(files, &mut lockfile, import_map, import_map_base);
+    lockfile.save(lockfile_path);
+
+    cli::write_output(&cli, &project)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// A scratch directory under the OS temp dir, unique per test and wiped
+    /// before use so re-runs don't see stale files from a previous run.
+    fn temp_project(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("jsa-main-test-{name}"));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
 
-    project.traverse(files);
+    #[test]
+    fn detects_a_circular_import_chain() {
+        let dir = temp_project("circular-import");
+        fs::write(dir.join("a.ts"), r#"import "./b";"#).unwrap();
+        fs::write(dir.join("b.ts"), r#"import "./a";"#).unwrap();
+        let mut lockfile = Lockfile::default();
+        let mut project = Project::default();
 
-    println!("{}", serde_json::to_string_pretty(&project).unwrap());
+        project.traverse(
+            vec![dir.join("a.ts").to_string_lossy().into_owned()],
+            &mut lockfile,
+            HashMap::new(),
+            PathBuf::from("."),
+        );
+
+        assert_eq!(project.circular_imports.len(), 1);
+        let cycle = &project.circular_imports[0];
+        assert!(cycle.chain.iter().any(|p| p.ends_with("a.ts")));
+        assert!(cycle.chain.iter().any(|p| p.ends_with("b.ts")));
+    }
+
+    fn parse_module(source: &str) -> ModuleFile {
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path(Path::new("test.ts")).unwrap();
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        let mut module = ModuleFile::default();
+        module.visit_program(&ret.program);
+        module
+    }
+
+    #[test]
+    fn extracts_local_declaration_exports_and_default_export() {
+        let module = parse_module("export const x = 1; export function f() {} export default class Foo {}");
+
+        let names: Vec<&str> = module.exports.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["x", "f"]);
+        assert!(module.exports.iter().all(|e| e.re_exported_from.is_none()));
+        assert_eq!(module.default_export, Some("Foo".to_string()));
+    }
+
+    #[test]
+    fn extracts_named_and_renamed_re_exports() {
+        let module = parse_module(r#"export { a, b as c } from "./other";"#);
+
+        let a = module.exports.iter().find(|e| e.name == "a").unwrap();
+        assert_eq!(a.re_exported_from.as_deref(), Some("./other"));
+        assert_eq!(a.re_exported_name.as_deref(), Some("a"));
+        assert!(!a.is_namespace_reexport);
+
+        let c = module.exports.iter().find(|e| e.name == "c").unwrap();
+        assert_eq!(c.re_exported_from.as_deref(), Some("./other"));
+        assert_eq!(c.re_exported_name.as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn extracts_blanket_and_namespaced_export_star() {
+        let module = parse_module(r#"export * from "./all"; export * as ns from "./named";"#);
+
+        let blanket = module.exports.iter().find(|e| e.name == "*").unwrap();
+        assert_eq!(blanket.re_exported_from.as_deref(), Some("./all"));
+        assert!(blanket.is_namespace_reexport);
+
+        let namespaced = module.exports.iter().find(|e| e.name == "ns").unwrap();
+        assert_eq!(namespaced.re_exported_from.as_deref(), Some("./named"));
+        assert!(namespaced.is_namespace_reexport);
+    }
+
+    #[test]
+    fn flags_unused_exports_across_files_but_not_imported_ones() {
+        let dir = temp_project("unused-exports");
+        fs::write(dir.join("entry.ts"), r#"import { used } from "./lib";"#).unwrap();
+        fs::write(
+            dir.join("lib.ts"),
+            "export const used = 1; export const unused = 2;",
+        )
+        .unwrap();
+        let mut lockfile = Lockfile::default();
+        let mut project = Project::default();
+
+        project.traverse(
+            vec![dir.join("entry.ts").to_string_lossy().into_owned()],
+            &mut lockfile,
+            HashMap::new(),
+            PathBuf::from("."),
+        );
+
+        let lib = project
+            .files
+            .iter()
+            .find(|f| f.path.ends_with("lib.ts"))
+            .unwrap();
+        assert_eq!(lib.unused_exports, vec!["unused".to_string()]);
+
+        let entry = project
+            .files
+            .iter()
+            .find(|f| f.path.ends_with("entry.ts"))
+            .unwrap();
+        assert!(entry.unused_exports.is_empty());
+    }
 }
@@ -0,0 +1,414 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// File extensions tried, in order, when a specifier doesn't resolve as-is.
+const EXTENSIONS: &[&str] = &[".ts", ".tsx", ".js", ".jsx", ".mjs", ".cjs"];
+
+/// Resolves raw import specifiers (relative, absolute, or bare) to concrete
+/// file paths on disk, following the same broad strokes as Node/TS module
+/// resolution: try the path as-is, then with each candidate extension, then
+/// as a directory index, and finally by walking up `node_modules`.
+#[derive(Debug)]
+pub struct Resolver {
+    /// Alias prefixes (tsconfig `paths` or an import map), longest first so
+    /// the most specific entry wins when several prefixes match.
+    import_map: Vec<(String, String)>,
+    /// Directory alias targets are resolved against: the tsconfig's
+    /// `baseUrl` (joined onto the tsconfig's own directory) or, for a plain
+    /// import map, the map file's directory.
+    base_dir: PathBuf,
+}
+
+impl Resolver {
+    pub fn with_import_map(import_map: HashMap<String, String>, base_dir: PathBuf) -> Self {
+        let mut entries: Vec<(String, String)> = import_map.into_iter().collect();
+        entries.sort_by_key(|(prefix, _)| Reverse(prefix.len()));
+        Self {
+            import_map: entries,
+            base_dir,
+        }
+    }
+
+    /// A stable string summarizing every input that affects resolution
+    /// results, for the lockfile to fold into its cache key alongside the
+    /// source hash — so a changed `--tsconfig`/`--import-map` invalidates
+    /// cached `resolved_path`s even when the source text hasn't changed.
+    pub fn config_fingerprint(&self) -> String {
+        let mut entries: Vec<String> = self
+            .import_map
+            .iter()
+            .map(|(prefix, target)| format!("{prefix}={target}"))
+            .collect();
+        entries.sort();
+        format!("{}|{}", self.base_dir.display(), entries.join(";"))
+    }
+
+    /// Resolve `specifier` as imported from `importer`, returning an absolute
+    /// path to the file it points at, or `None` if nothing on disk matches.
+    pub fn resolve(&self, importer: &Path, specifier: &str) -> Option<PathBuf> {
+        if let Some(aliased) = self.apply_import_map(specifier) {
+            return self.resolve_aliased(&aliased);
+        }
+        if specifier.starts_with('.') || specifier.starts_with('/') {
+            let base = if specifier.starts_with('/') {
+                PathBuf::from(specifier)
+            } else {
+                importer.parent().unwrap_or(Path::new(".")).join(specifier)
+            };
+            self.resolve_file_or_dir(&base)
+        } else {
+            self.resolve_node_modules(importer, specifier)
+        }
+    }
+
+    /// Match `specifier` against the import map by longest key prefix,
+    /// substituting the remainder onto the mapped target (the same scheme
+    /// as TypeScript's `compilerOptions.paths` and Deno's import maps). Only
+    /// a prefix ending in `/` (a wildcard entry like `"@/*"`, stripped down
+    /// to `"@/"`) matches as a prefix; any other entry must match exactly,
+    /// so an exact alias like `"@utils"` doesn't also swallow `"@utilsHelper"`.
+    fn apply_import_map(&self, specifier: &str) -> Option<String> {
+        for (prefix, target) in &self.import_map {
+            if specifier == prefix {
+                return Some(target.clone());
+            }
+            if !prefix.ends_with('/') {
+                continue;
+            }
+            if let Some(rest) = specifier.strip_prefix(prefix.as_str()) {
+                return Some(format!("{target}{rest}"));
+            }
+        }
+        None
+    }
+
+    /// An import-map target is always a path resolved against `base_dir`
+    /// (the tsconfig's `baseUrl` or the map file's own directory), never the
+    /// importing file and never a bare specifier into `node_modules` — even
+    /// when written without a leading `./`, as tsconfig `paths` conventionally
+    /// are (e.g. `"@/*": ["src/*"]`).
+    fn resolve_aliased(&self, target: &str) -> Option<PathBuf> {
+        let base = if target.starts_with('/') {
+            PathBuf::from(target)
+        } else {
+            self.base_dir.join(target)
+        };
+        self.resolve_file_or_dir(&base)
+    }
+
+    /// Try `candidate` as a file (with extension candidates) and, failing
+    /// that, as a directory with an `index.*` file.
+    fn resolve_file_or_dir(&self, candidate: &Path) -> Option<PathBuf> {
+        if candidate.is_file() {
+            return Some(normalize(candidate));
+        }
+        for ext in EXTENSIONS {
+            let with_ext = append_ext(candidate, ext);
+            if with_ext.is_file() {
+                return Some(normalize(&with_ext));
+            }
+        }
+        if candidate.is_dir() {
+            for ext in EXTENSIONS {
+                let index = candidate.join(format!("index{ext}"));
+                if index.is_file() {
+                    return Some(normalize(&index));
+                }
+            }
+        }
+        None
+    }
+
+    /// Walk up from the importer's directory through every `node_modules`,
+    /// resolving `specifier` against the package's `exports`/`main`/`module`
+    /// fields (falling back to the subpath, or `index` for the package root,
+    /// tried with each candidate extension).
+    fn resolve_node_modules(&self, importer: &Path, specifier: &str) -> Option<PathBuf> {
+        let start = importer.parent().unwrap_or(Path::new("."));
+        self.resolve_node_modules_from_dir(start, specifier)
+    }
+
+    /// Same as [`Self::resolve_node_modules`], but starting the upward walk
+    /// at `start` directly rather than at an importing file's parent.
+    fn resolve_node_modules_from_dir(&self, start: &Path, specifier: &str) -> Option<PathBuf> {
+        let (package_name, subpath) = split_specifier(specifier);
+        let mut dir = Some(start.to_path_buf());
+
+        while let Some(current) = dir {
+            let package_dir = current.join("node_modules").join(package_name);
+            if package_dir.is_dir() {
+                if let Some(resolved) = self.resolve_package(&package_dir, subpath) {
+                    return Some(resolved);
+                }
+            }
+            dir = current.parent().map(Path::to_path_buf);
+        }
+        None
+    }
+
+    /// Resolve `subpath` (empty for the package root) inside `package_dir`
+    /// using its `package.json` `exports`, `main`, or `module` field.
+    fn resolve_package(&self, package_dir: &Path, subpath: &str) -> Option<PathBuf> {
+        let manifest_path = package_dir.join("package.json");
+        if let Ok(manifest) = std::fs::read_to_string(&manifest_path) {
+            if let Ok(manifest) = serde_json::from_str::<serde_json::Value>(&manifest) {
+                if !subpath.is_empty() {
+                    if let Some(target) = manifest
+                        .get("exports")
+                        .and_then(|exports| lookup_exports_subpath(exports, subpath))
+                    {
+                        return self.resolve_file_or_dir(&package_dir.join(target));
+                    }
+                } else if let Some(entry) = manifest
+                    .get("exports")
+                    .and_then(entry_from_exports)
+                    .or_else(|| manifest.get("main").and_then(|v| v.as_str()))
+                    .or_else(|| manifest.get("module").and_then(|v| v.as_str()))
+                {
+                    if let Some(resolved) = self.resolve_file_or_dir(&package_dir.join(entry)) {
+                        return Some(resolved);
+                    }
+                }
+            }
+        }
+        self.resolve_file_or_dir(&package_dir.join(if subpath.is_empty() {
+            "index"
+        } else {
+            subpath
+        }))
+    }
+}
+
+/// Split a bare specifier into its package name and remaining subpath, e.g.
+/// `"@scope/pkg/lib/foo"` -> `("@scope/pkg", "lib/foo")`. A specifier that
+/// merely starts with `@` but has no `scope/name` segment (e.g.
+/// `"@weirdSpecifier"`) isn't actually scoped and is treated as an ordinary
+/// unscoped name instead.
+fn split_specifier(specifier: &str) -> (&str, &str) {
+    let is_scoped = specifier.starts_with('@') && specifier[1..].contains('/');
+    let mut parts = specifier.splitn(if is_scoped { 3 } else { 2 }, '/');
+    let first = parts.next().unwrap_or("");
+    if is_scoped {
+        let second = parts.next().unwrap_or("");
+        let name_len = (first.len() + 1 + second.len()).min(specifier.len());
+        let rest = specifier.get(name_len + 1..).unwrap_or("");
+        (&specifier[..name_len], rest)
+    } else {
+        let rest = specifier.get(first.len() + 1..).unwrap_or("");
+        (first, rest)
+    }
+}
+
+/// Pull the package's root entry point out of its `exports` field, which may
+/// be a plain string or a conditions object/map.
+fn entry_from_exports(exports: &serde_json::Value) -> Option<&str> {
+    match exports {
+        serde_json::Value::String(s) => Some(s),
+        serde_json::Value::Object(map) => map
+            .get(".")
+            .and_then(entry_from_exports)
+            .or_else(|| map.get("import").and_then(entry_from_exports))
+            .or_else(|| map.get("default").and_then(entry_from_exports)),
+        _ => None,
+    }
+}
+
+/// Look up `"./{subpath}"` inside an `exports` map, following the same
+/// condition fallback order as [`entry_from_exports`].
+fn lookup_exports_subpath<'a>(exports: &'a serde_json::Value, subpath: &str) -> Option<&'a str> {
+    let key = format!("./{subpath}");
+    exports.as_object()?.get(&key).and_then(entry_from_exports)
+}
+
+/// Load an alias table from either a tsconfig.json (`compilerOptions.paths`)
+/// or an import-map JSON file (`{"imports": {...}}` or a flat `{...}` map),
+/// along with the directory alias targets should be resolved against: a
+/// tsconfig's `compilerOptions.baseUrl` (relative to the tsconfig's own
+/// directory), or the import-map file's own directory. Returns an empty map
+/// if the file is missing or doesn't match either shape.
+pub fn load_import_map(path: &Path) -> (HashMap<String, String>, PathBuf) {
+    let config_dir = path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    let Ok(text) = std::fs::read_to_string(path) else {
+        return (HashMap::new(), config_dir);
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&text) else {
+        return (HashMap::new(), config_dir);
+    };
+
+    if let Some(paths) = json
+        .get("compilerOptions")
+        .and_then(|c| c.get("paths"))
+        .and_then(|p| p.as_object())
+    {
+        let map = paths
+            .iter()
+            .filter_map(|(key, targets)| {
+                let target = targets.as_array()?.first()?.as_str()?;
+                Some((strip_wildcard(key), strip_wildcard(target)))
+            })
+            .collect();
+        let base_url = json
+            .get("compilerOptions")
+            .and_then(|c| c.get("baseUrl"))
+            .and_then(|b| b.as_str())
+            .unwrap_or(".");
+        return (map, config_dir.join(base_url));
+    }
+
+    let table = json
+        .get("imports")
+        .and_then(|i| i.as_object())
+        .or_else(|| json.as_object());
+    let map = table
+        .map(|table| {
+            table
+                .iter()
+                .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+    (map, config_dir)
+}
+
+/// tsconfig `paths` entries use a trailing `*` wildcard (`"@/*"` ->
+/// `"./src/*"`); strip it so the remaining text is a plain prefix.
+fn strip_wildcard(pattern: &str) -> String {
+    pattern.strip_suffix('*').unwrap_or(pattern).to_string()
+}
+
+fn append_ext(path: &Path, ext: &str) -> PathBuf {
+    let mut s = path.as_os_str().to_os_string();
+    s.push(ext);
+    PathBuf::from(s)
+}
+
+/// Canonicalize `path` when possible, falling back to it unchanged (e.g. for
+/// a seed entry that doesn't exist on disk) so callers can dedupe entries
+/// and resolved import targets on a common, absolute representation.
+pub fn normalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// A scratch directory under the OS temp dir, unique per test and wiped
+    /// before use so re-runs don't see stale files from a previous run.
+    fn temp_project(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("jsa-resolver-test-{name}"));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn resolver_without_aliases() -> Resolver {
+        Resolver::with_import_map(HashMap::new(), PathBuf::from("."))
+    }
+
+    #[test]
+    fn resolves_relative_specifier_by_trying_extensions() {
+        let dir = temp_project("relative-ext");
+        fs::write(dir.join("foo.ts"), "export const x = 1;").unwrap();
+        let importer = dir.join("index.ts");
+
+        let resolved = resolver_without_aliases().resolve(&importer, "./foo").unwrap();
+
+        assert_eq!(resolved, dir.join("foo.ts").canonicalize().unwrap());
+    }
+
+    #[test]
+    fn resolves_directory_to_its_index_file() {
+        let dir = temp_project("dir-index");
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub").join("index.ts"), "export const y = 1;").unwrap();
+        let importer = dir.join("main.ts");
+
+        let resolved = resolver_without_aliases().resolve(&importer, "./sub").unwrap();
+
+        assert_eq!(resolved, dir.join("sub").join("index.ts").canonicalize().unwrap());
+    }
+
+    #[test]
+    fn resolves_scoped_package_via_exports_field() {
+        let dir = temp_project("scoped-pkg");
+        let pkg_dir = dir.join("node_modules").join("@scope").join("pkg");
+        fs::create_dir_all(&pkg_dir).unwrap();
+        fs::write(pkg_dir.join("package.json"), r#"{"exports": "./index.js"}"#).unwrap();
+        fs::write(pkg_dir.join("index.js"), "module.exports = {};").unwrap();
+        let importer = dir.join("main.ts");
+
+        let resolved = resolver_without_aliases()
+            .resolve(&importer, "@scope/pkg")
+            .unwrap();
+
+        assert_eq!(resolved, pkg_dir.join("index.js").canonicalize().unwrap());
+    }
+
+    #[test]
+    fn does_not_panic_on_an_at_prefixed_specifier_with_no_slash() {
+        let dir = temp_project("at-prefixed-no-slash");
+        let importer = dir.join("main.ts");
+
+        let resolved = resolver_without_aliases().resolve(&importer, "@weirdSpecifier");
+
+        assert_eq!(resolved, None);
+    }
+
+    #[test]
+    fn applies_longest_matching_alias_prefix() {
+        let dir = temp_project("alias-prefix");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src").join("utils.ts"), "export const u = 1;").unwrap();
+        fs::write(dir.join("src").join("other.ts"), "export const o = 1;").unwrap();
+        let mut import_map = HashMap::new();
+        import_map.insert("@/".to_string(), "./src/".to_string());
+        import_map.insert("@/utils".to_string(), "./src/utils".to_string());
+        let resolver = Resolver::with_import_map(import_map, dir.clone());
+        let importer = dir.join("main.ts");
+
+        let via_specific = resolver.resolve(&importer, "@/utils").unwrap();
+        let via_general = resolver.resolve(&importer, "@/other").unwrap();
+
+        assert_eq!(via_specific, dir.join("src").join("utils.ts").canonicalize().unwrap());
+        assert_eq!(via_general, dir.join("src").join("other.ts").canonicalize().unwrap());
+    }
+
+    #[test]
+    fn exact_alias_without_trailing_slash_does_not_match_as_a_prefix() {
+        let dir = temp_project("alias-exact-not-prefix");
+        let mut import_map = HashMap::new();
+        import_map.insert("@utils".to_string(), "./src/utils".to_string());
+        let resolver = Resolver::with_import_map(import_map, dir.clone());
+        let importer = dir.join("main.ts");
+
+        assert!(resolver.resolve(&importer, "@utilsHelper").is_none());
+    }
+
+    #[test]
+    fn resolves_tsconfig_paths_target_written_without_leading_dot() {
+        let dir = temp_project("tsconfig-bare-target");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src").join("helper.ts"), "export const h = 1;").unwrap();
+        let tsconfig_path = dir.join("tsconfig.json");
+        fs::write(
+            &tsconfig_path,
+            r#"{"compilerOptions": {"baseUrl": ".", "paths": {"@/*": ["src/*"]}}}"#,
+        )
+        .unwrap();
+
+        let (import_map, base_dir) = load_import_map(&tsconfig_path);
+        let resolver = Resolver::with_import_map(import_map, base_dir);
+        let importer = dir.join("main.ts");
+
+        let resolved = resolver.resolve(&importer, "@/helper").unwrap();
+
+        assert_eq!(resolved, dir.join("src").join("helper.ts").canonicalize().unwrap());
+    }
+}